@@ -1,16 +1,18 @@
 use std::fmt::Write as _;
 use std::fs::{File, Metadata};
-use std::io::{self, BufReader, Read, Write};
+use std::io::{self, BufRead, BufReader, Read, Write};
 use std::path::{Path, PathBuf};
 
 use clap::Parser;
+use memchr::memchr_iter;
 use rayon::prelude::*;
+use unicode_width::UnicodeWidthChar;
 use walkdir::WalkDir;
 
 #[derive(Parser)]
 #[clap(author="LuminousToaster", version=env!("CARGO_PKG_VERSION"), about="A rewrite of the GNU coreutils 'wc' tool.", long_about = None)]
 struct Args {
-	#[clap(value_parser, help="The file or folder to read", required=true)]
+	#[clap(value_parser, help="The file or folder to read; '-' or omitted reads standard input")]
 	file: Vec<String>,
 	#[clap(short='c', long, help="Print the byte counts")]
 	bytes: bool,
@@ -24,10 +26,32 @@ struct Args {
 	max_line_length: bool,
 	#[clap(short='r', long, help="Recursively search through folders and files")]
 	recursive: bool,
+	#[clap(long, value_name="F", help="Read NUL-separated file names from F (or stdin if F is '-') instead of the command line")]
+	files0_from: Option<String>,
+	#[clap(short='z', long, help="Transparently decompress gzip/zstd/xz input before counting")]
+	decompress: bool,
+	#[clap(short='P', long, help="Print GNU wc-compatible right-aligned columns (lines words chars bytes max-line-length) instead of labeled fields")]
+	posix: bool,
 	#[clap(short='v', help="Print verbose output")]
 	verbose: bool,
 }
 
+// A source to count: either a file on disk or standard input (the `-` pseudo-file)
+#[derive(Clone)]
+enum Input {
+	File(PathBuf),
+	Stdin,
+}
+
+impl Input {
+	fn display_name(&self) -> String {
+		match self {
+			Input::File(path) => path.display().to_string(),
+			Input::Stdin => "-".to_string(),
+		}
+	}
+}
+
 // Struct to hold count data
 #[derive(Default, Clone)]
 struct Counts {
@@ -48,6 +72,95 @@ impl Counts {
 	}
 }
 
+// Tracks `-L` display-width column position; `partial` carries a UTF-8 sequence split across a 1MB read boundary.
+#[derive(Default)]
+struct LineWidthState {
+	partial: Vec<u8>,
+	current_line_length: usize,
+	max_line_length: usize,
+}
+
+impl LineWidthState {
+	fn scan(&mut self, buffer: &[u8]) {
+		let mut i = 0;
+		// Finish off a sequence that was split across the previous read.
+		if !self.partial.is_empty() {
+			let want = utf8_seq_len(self.partial[0]);
+			while self.partial.len() < want && i < buffer.len() {
+				self.partial.push(buffer[i]);
+				i += 1;
+			}
+			if self.partial.len() == want {
+				self.advance(decode_char(&self.partial));
+				self.partial.clear();
+			} else {
+				// Still incomplete and the buffer ran out; keep waiting.
+				return;
+			}
+		}
+		while i < buffer.len() {
+			let lead = buffer[i];
+			let want = utf8_seq_len(lead);
+			if i + want > buffer.len() {
+				self.partial.extend_from_slice(&buffer[i..]);
+				break;
+			}
+			self.advance(decode_char(&buffer[i..i + want]));
+			i += want;
+		}
+	}
+
+	fn advance(&mut self, c: char) {
+		match c {
+			'\n' => {
+				self.max_line_length = self.max_line_length.max(self.current_line_length);
+				self.current_line_length = 0;
+			}
+			'\t' => self.current_line_length += 8 - (self.current_line_length % 8),
+			_ => self.current_line_length += char_display_width(c),
+		}
+	}
+
+	fn finish(mut self) -> usize {
+		if !self.partial.is_empty() {
+			self.advance(decode_char(&self.partial));
+		}
+		self.max_line_length.max(self.current_line_length)
+	}
+}
+
+// Number of bytes in the UTF-8 sequence starting with `lead` (invalid lead bytes count as 1, like GNU wc)
+#[inline]
+fn utf8_seq_len(lead: u8) -> usize {
+	if lead & 0x80 == 0 {
+		1
+	} else if lead & 0xE0 == 0xC0 {
+		2
+	} else if lead & 0xF0 == 0xE0 {
+		3
+	} else if lead & 0xF8 == 0xF0 {
+		4
+	} else {
+		1
+	}
+}
+
+// Decodes one UTF-8 sequence, falling back to the replacement character on invalid input
+#[inline]
+fn decode_char(bytes: &[u8]) -> char {
+	std::str::from_utf8(bytes).ok().and_then(|s| s.chars().next()).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+// wcwidth-style column count: 0 for control/combining, 2 for wide CJK, 1 otherwise
+#[inline]
+fn char_display_width(c: char) -> usize {
+	if c.is_control() {
+		0
+	} else {
+		UnicodeWidthChar::width(c).unwrap_or(0)
+	}
+}
+
 static IS_WHITESPACE: [bool; 256] = {
 	let mut table = [false; 256];
 	table[b' ' as usize] = true;
@@ -57,6 +170,27 @@ static IS_WHITESPACE: [bool; 256] = {
 	table
 };
 
+// Files at or above this size get split into per-CPU chunks and scanned in parallel (see process_file_chunked)
+const PARALLEL_CHUNK_THRESHOLD: u64 = 8 * 1024 * 1024;
+
+// Per-chunk scan result for the within-file parallel path: the plain sums plus the
+// boundary facts needed to stitch chunks back together across a word, line, or UTF-8 split.
+#[derive(Default, Clone)]
+struct ChunkResult {
+	bytes: usize,
+	chars: usize,
+	lines: usize,
+	words: usize,
+	chunk_max_line_length: usize,
+	starts_whitespace: bool,
+	ends_whitespace: bool,
+	has_newline: bool,
+	leading_width: usize,
+	trailing_width: usize,
+	leading_continuation: Vec<u8>,
+	trailing_partial: Vec<u8>,
+}
+
 #[inline(always)]
 fn main() -> io::Result<()> {
 	rayon::ThreadPoolBuilder::new().num_threads(num_cpus::get()).build_global().unwrap();
@@ -73,73 +207,351 @@ fn main() -> io::Result<()> {
 	let stdout = io::stdout();
 	let mut stdout = stdout.lock();
 
-	// Collect paths and metadata
-	let paths: Vec<(PathBuf, Option<Metadata>)> = app.file.iter().flat_map(|file_path| {
-		WalkDir::new(file_path)	.sort_by_file_name().into_iter().filter_map(Result::ok).take(if app.recursive { usize::MAX } else { 1 }).map(|entry| {
-			let path = entry.into_path();
-			let metadata = std::fs::metadata(&path).ok();
-			(path, metadata)})
-	}).collect();
+	// Collect inputs and metadata; no paths (or a literal '-') means stdin
+	let paths: Vec<(Input, Option<Metadata>)> = if let Some(list_file) = &app.files0_from {
+		if !app.file.is_empty() {
+			writeln!(io::stderr(), "ripwc: file operands cannot be combined with --files0-from")?;
+			std::process::exit(1);
+		}
+		collect_files0_from(list_file)?
+	} else if app.file.is_empty() {
+		vec![(Input::Stdin, None)]
+	} else {
+		app.file.iter().flat_map(|file_path| -> Box<dyn Iterator<Item = (Input, Option<Metadata>)>> {
+			if file_path == "-" {
+				Box::new(std::iter::once((Input::Stdin, None)))
+			} else {
+				Box::new(WalkDir::new(file_path).sort_by_file_name().into_iter().filter_map(Result::ok).take(if app.recursive { usize::MAX } else { 1 }).map(|entry| {
+					let path = entry.into_path();
+					let metadata = std::fs::metadata(&path).ok();
+					(Input::File(path), metadata)
+				}))
+			}
+		}).collect()
+	};
 
 	// Process files in parallel with small-file batching
-	let counts: Vec<(Counts, String)> = paths.par_iter().filter_map(|(path, metadata)| {
+	let counts: Vec<(Counts, String)> = paths.par_iter().filter_map(|(input, metadata)| {
 		// Batch small files (<512KB) sequentially
 		if let Some(meta) = metadata {
 			if meta.len() < 512 * 1024 {
-				let mut counts = Counts::default();
-				let result = process_file(path, Some(meta), &app).ok()?;
-				counts.add(&result);
-				let mut output = String::with_capacity(128);
-				append_counts(&mut output, &counts, &app);
-				return Some((counts, format!("{} {}", output.trim(), path.display())));
+				let counts = process_file(input, Some(meta), &app).ok()?;
+				return Some((counts, input.display_name()));
 			}
 		}
 		if app.verbose {
-			writeln!(io::stderr(), "Processing: {}", path.display()).ok()?;
+			writeln!(io::stderr(), "Processing: {}", input.display_name()).ok()?;
 		}
-		let counts = process_file(path, metadata.as_ref(), &app).ok()?;
-		let mut result = String::with_capacity(128);
-		append_counts(&mut result, &counts, &app);
-		Some((counts, format!("{} {}", result.trim(), path.display())))
+		let counts = process_file(input, metadata.as_ref(), &app).ok()?;
+		Some((counts, input.display_name()))
 	}).collect();
-	// Aggregate results
-	for (counts, output) in counts {
+	for (counts, _) in &counts {
 		file_count += 1;
-		total_counts.add(&counts);
-		writeln!(stdout, "{}", output)?;
+		total_counts.add(counts);
+	}
+	// Posix mode needs every field's width up front, since it's a fixed-width column,
+	// so results are buffered (already collected above) before anything is printed.
+	let posix_width = app.posix.then(|| posix_column_width(&counts, &total_counts, &app));
+	for (counts, name) in &counts {
+		result.clear();
+		match posix_width {
+			// Posix columns are already exactly right-justified; trimming would eat
+			// the leading alignment space a short number is padded with.
+			Some(width) => {
+				append_counts_posix(&mut result, counts, &app, width);
+				writeln!(stdout, "{} {}", result, name)?;
+			}
+			None => {
+				append_counts(&mut result, counts, &app);
+				writeln!(stdout, "{} {}", result.trim(), name)?;
+			}
+		}
 	}
 	// Print totals if multiple files were processed
 	if file_count > 1 {
 		result.clear();
-		append_counts(&mut result, &total_counts, &app);
-		writeln!(stdout, "\n{} total", result.trim())?;
+		match posix_width {
+			Some(width) => {
+				append_counts_posix(&mut result, &total_counts, &app, width);
+				writeln!(stdout, "\n{} total", result)?;
+			}
+			None => {
+				append_counts(&mut result, &total_counts, &app);
+				writeln!(stdout, "\n{} total", result.trim())?;
+			}
+		}
 	}
 	Ok(())
 }
 
-// Process a single file and return its counts
+// Reads a NUL-separated file list for `--files0-from` (F, or stdin when F is '-')
+fn collect_files0_from(list_file: &str) -> io::Result<Vec<(Input, Option<Metadata>)>> {
+	let mut list = Vec::new();
+	if list_file == "-" {
+		io::stdin().read_to_end(&mut list)?;
+	} else {
+		File::open(list_file)?.read_to_end(&mut list)?;
+	}
+	let names: Vec<&[u8]> = list.split(|&b| b == 0).collect();
+	let last = names.len().saturating_sub(1);
+	let mut inputs = Vec::new();
+	for (i, name) in names.into_iter().enumerate() {
+		if name.is_empty() {
+			// A trailing NUL just terminates the list; any other empty entry is a real zero-length name
+			if i != last {
+				writeln!(io::stderr(), "ripwc: {}: invalid zero-length file name", list_file)?;
+			}
+			continue;
+		}
+		let name = String::from_utf8_lossy(name);
+		if name == "-" {
+			inputs.push((Input::Stdin, None));
+			continue;
+		}
+		let path = PathBuf::from(name.as_ref());
+		let metadata = std::fs::metadata(&path).ok();
+		inputs.push((Input::File(path), metadata));
+	}
+	Ok(inputs)
+}
+
+// Wraps `source` (a file or stdin) in a streaming gzip/zstd/xz decoder based on its
+// magic bytes, or passes it through unchanged when none match. On-disk size no
+// longer equals the byte count once decompressed, so callers must skip the
+// `Metadata::len()` fast path when `-z` is set.
+fn open_decompressed<R: Read + 'static>(source: R) -> io::Result<Box<dyn Read>> {
+	let mut peek = BufReader::new(source);
+	let magic = peek.fill_buf()?;
+	if magic.starts_with(&[0x1f, 0x8b]) {
+		Ok(Box::new(flate2::read::GzDecoder::new(peek)))
+	} else if magic.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+		Ok(Box::new(zstd::stream::read::Decoder::new(peek)?))
+	} else if magic.starts_with(&[0xfd, 0x37, 0x7a, 0x58, 0x5a]) {
+		Ok(Box::new(xz2::read::XzDecoder::new(peek)))
+	} else {
+		Ok(Box::new(peek))
+	}
+}
+
+// Splits a large file into num_cpus chunks, scans each off-thread, and merges the results
+fn process_file_chunked(path: &Path, app: &Args) -> io::Result<Counts> {
+	let data = std::fs::read(path)?;
+	let num_chunks = num_cpus::get().max(1);
+	let chunk_size = data.len().div_ceil(num_chunks).max(1);
+	let results: Vec<ChunkResult> = data.par_chunks(chunk_size).map(|chunk| scan_chunk(chunk, app)).collect();
+	Ok(merge_chunk_results(&results))
+}
+
+// Scans one in-memory chunk, recording the boundary facts merge_chunk_results needs
+fn scan_chunk(data: &[u8], app: &Args) -> ChunkResult {
+	let mut result = ChunkResult::default();
+	if data.is_empty() {
+		return result;
+	}
+	result.starts_whitespace = IS_WHITESPACE[data[0] as usize];
+	result.ends_whitespace = IS_WHITESPACE[*data.last().unwrap() as usize];
+
+	if app.bytes {
+		result.bytes = data.len();
+	}
+	if app.chars {
+		result.chars = data.iter().filter(|&&b| b & 0xC0 != 0x80).count();
+	}
+	if app.lines {
+		result.lines = memchr_iter(b'\n', data).count();
+	}
+	if app.words {
+		let mut in_word = false;
+		for &b in data {
+			let ws = IS_WHITESPACE[b as usize];
+			if !in_word && !ws {
+				in_word = true;
+			} else if in_word && ws {
+				in_word = false;
+				result.words += 1;
+			}
+		}
+		if in_word {
+			result.words += 1;
+		}
+	}
+	if app.max_line_length {
+		let width = scan_chunk_width(data);
+		result.chunk_max_line_length = width.chunk_max;
+		result.has_newline = width.has_newline;
+		result.leading_width = width.leading;
+		result.trailing_width = width.trailing;
+		result.leading_continuation = width.leading_continuation;
+		result.trailing_partial = width.trailing_partial;
+	}
+	result
+}
+
+// Width pieces from scanning one chunk; leading/trailing exclude any UTF-8 sequence
+// cut off by the chunk boundary, since that dangling byte or two is resolved at merge time.
+struct ChunkWidth {
+	chunk_max: usize,
+	leading: usize,
+	trailing: usize,
+	has_newline: bool,
+	leading_continuation: Vec<u8>,
+	trailing_partial: Vec<u8>,
+}
+
+// Decodes a chunk's display width, same width/newline tracking as LineWidthState but
+// carrying a split UTF-8 sequence at either edge back out to the caller instead of
+// decoding it alone (which would truncate into U+FFFD)
+fn scan_chunk_width(data: &[u8]) -> ChunkWidth {
+	let mut current = 0usize;
+	let mut chunk_max = 0usize;
+	let mut leading = 0usize;
+	let mut has_newline = false;
+
+	// Stray continuation bytes at the very start belong to the previous chunk's split char
+	let mut i = 0;
+	let mut leading_continuation = Vec::new();
+	while i < data.len() && data[i] & 0xC0 == 0x80 {
+		leading_continuation.push(data[i]);
+		i += 1;
+	}
+
+	let mut trailing_partial = Vec::new();
+	while i < data.len() {
+		let want = utf8_seq_len(data[i]);
+		if i + want > data.len() {
+			trailing_partial.extend_from_slice(&data[i..]);
+			break;
+		}
+		let c = decode_char(&data[i..i + want]);
+		i += want;
+		match c {
+			'\n' => {
+				if has_newline {
+					chunk_max = chunk_max.max(current);
+				} else {
+					leading = current;
+					has_newline = true;
+				}
+				current = 0;
+			}
+			'\t' => current += 8 - (current % 8),
+			_ => current += char_display_width(c),
+		}
+	}
+	if !has_newline {
+		leading = current;
+	}
+	ChunkWidth { chunk_max, leading, trailing: current, has_newline, leading_continuation, trailing_partial }
+}
+
+// Sums the per-chunk counts and reconciles the boundary facts: a word split across a
+// chunk split was flushed once on each side, so adjacent chunks that are mid-word at
+// the split lose one. Line width is folded across a whole run of chunks at once (not
+// just adjacent pairs), carrying both the running line width and any dangling UTF-8
+// sequence forward chunk by chunk, so a line or character spanning 3+ chunks is still
+// counted as one.
+fn merge_chunk_results(results: &[ChunkResult]) -> Counts {
+	let mut counts = Counts::default();
+	for r in results {
+		counts.bytes += r.bytes;
+		counts.chars += r.chars;
+		counts.lines += r.lines;
+		counts.words += r.words;
+	}
+	for pair in results.windows(2) {
+		let (prev, next) = (&pair[0], &pair[1]);
+		if !prev.ends_whitespace && !next.starts_whitespace {
+			counts.words = counts.words.saturating_sub(1);
+		}
+	}
+
+	let mut max_line_length = 0usize;
+	let mut carry = 0usize;
+	let mut pending_tail: Vec<u8> = Vec::new();
+	for r in results {
+		let mut boundary_bytes = std::mem::take(&mut pending_tail);
+		boundary_bytes.extend_from_slice(&r.leading_continuation);
+		// A chunk made only of continuation bytes sits inside one character; keep carrying it.
+		let boundary_incomplete = !boundary_bytes.is_empty() && boundary_bytes.len() < utf8_seq_len(boundary_bytes[0]);
+		if boundary_incomplete && !r.has_newline && r.leading_width == 0 && r.trailing_partial.is_empty() {
+			pending_tail = boundary_bytes;
+			continue;
+		}
+		let boundary_width = if boundary_bytes.is_empty() { 0 } else { char_display_width(decode_char(&boundary_bytes)) };
+
+		max_line_length = max_line_length.max(r.chunk_max_line_length);
+		if r.has_newline {
+			max_line_length = max_line_length.max(carry + boundary_width + r.leading_width);
+			carry = r.trailing_width;
+		} else {
+			carry += boundary_width + r.leading_width;
+		}
+		pending_tail = r.trailing_partial.clone();
+	}
+	if !pending_tail.is_empty() {
+		carry += char_display_width(decode_char(&pending_tail));
+	}
+	max_line_length = max_line_length.max(carry);
+
+	counts.max_line_length = max_line_length;
+	counts
+}
+
+// Process a single input (file or stdin) and return its counts
 #[inline(always)]
-fn process_file(path: &Path, metadata: Option<&Metadata>, app: &Args) -> io::Result<Counts> {
-	let file = File::open(path)?;
+fn process_file(input: &Input, metadata: Option<&Metadata>, app: &Args) -> io::Result<Counts> {
 	let mut counts = Counts::default();
-	// If only bytes are needed, use metadata
-	if app.bytes && !app.lines && !app.words && !app.chars && !app.max_line_length {
+	// If only bytes are needed, use metadata (stdin has none, and -z makes the on-disk size meaningless, so both always fall through to scanning)
+	if app.bytes && !app.lines && !app.words && !app.chars && !app.max_line_length && !app.decompress {
 		if let Some(meta) = metadata {
 			counts.bytes = meta.len() as usize;
 			return Ok(counts);
 		}
 	}
+	// Large on-disk files get scanned by multiple threads instead of one; skipped for
+	// stdin (no random access) and -z (on-disk size doesn't reflect decompressed size)
+	if let Input::File(path) = input {
+		if !app.decompress {
+			if let Some(meta) = metadata {
+				if meta.len() >= PARALLEL_CHUNK_THRESHOLD {
+					return process_file_chunked(path, app);
+				}
+			}
+		}
+	}
+	let source: Box<dyn Read> = match input {
+		Input::File(path) => {
+			let file = File::open(path)?;
+			if app.decompress { open_decompressed(file)? } else { Box::new(file) }
+		}
+		Input::Stdin if app.decompress => open_decompressed(io::stdin())?,
+		Input::Stdin => Box::new(io::stdin()),
+	};
 	// Use buffered reading with 1MB buffer on heap
-	let mut reader = BufReader::with_capacity(1024 * 1024, file);
+	let mut reader = BufReader::with_capacity(1024 * 1024, source);
 	let mut buffer = vec![0u8; 1024 * 1024];
 	let mut in_word = false;
-	let mut current_line_length = 0;
+	let mut width_state = LineWidthState::default();
 
 	loop {
 		let bytes_read = reader.read(&mut buffer)?;
 		if bytes_read == 0 {
 			break;
 		}
+		// Display width needs real UTF-8 decoding, so -L gets its own pass
+		// over the buffer rather than riding along with the byte unrolling.
+		if app.max_line_length {
+			width_state.scan(&buffer[..bytes_read]);
+		}
+		// The very common `-l`/`-lc` case needs no per-byte branching at all: let
+		// memchr's vectorized search count newlines and take bytes_read directly.
+		if app.lines && !app.words && !app.chars && !app.max_line_length {
+			counts.lines += memchr_iter(b'\n', &buffer[..bytes_read]).count();
+			if app.bytes {
+				counts.bytes += bytes_read;
+			}
+			continue;
+		}
 		// Process buffer with 8-byte unrolling
 		let mut i = 0;
 		unsafe {
@@ -157,14 +569,14 @@ fn process_file(path: &Path, metadata: Option<&Metadata>, app: &Args) -> io::Res
 					counts.bytes += 8;
 				}
 				if app.chars {
-					if b0 != 0 { counts.chars += 1; }
-					if b1 != 0 { counts.chars += 1; }
-					if b2 != 0 { counts.chars += 1; }
-					if b3 != 0 { counts.chars += 1; }
-					if b4 != 0 { counts.chars += 1; }
-					if b5 != 0 { counts.chars += 1; }
-					if b6 != 0 { counts.chars += 1; }
-					if b7 != 0 { counts.chars += 1; }
+					if b0 & 0xC0 != 0x80 { counts.chars += 1; }
+					if b1 & 0xC0 != 0x80 { counts.chars += 1; }
+					if b2 & 0xC0 != 0x80 { counts.chars += 1; }
+					if b3 & 0xC0 != 0x80 { counts.chars += 1; }
+					if b4 & 0xC0 != 0x80 { counts.chars += 1; }
+					if b5 & 0xC0 != 0x80 { counts.chars += 1; }
+					if b6 & 0xC0 != 0x80 { counts.chars += 1; }
+					if b7 & 0xC0 != 0x80 { counts.chars += 1; }
 				}
 				if app.lines {
 					if b0 == b'\n' { counts.lines += 1; }
@@ -176,56 +588,6 @@ fn process_file(path: &Path, metadata: Option<&Metadata>, app: &Args) -> io::Res
 					if b6 == b'\n' { counts.lines += 1; }
 					if b7 == b'\n' { counts.lines += 1; }
 				}
-				if app.max_line_length {
-					if b0 == b'\n' {
-						counts.max_line_length = counts.max_line_length.max(current_line_length);
-						current_line_length = 0;
-					} else {
-						current_line_length += 1;
-					}
-					if b1 == b'\n' {
-						counts.max_line_length = counts.max_line_length.max(current_line_length);
-						current_line_length = 0;
-					} else {
-						current_line_length += 1;
-					}
-					if b2 == b'\n' {
-						counts.max_line_length = counts.max_line_length.max(current_line_length);
-						current_line_length = 0;
-					} else {
-						current_line_length += 1;
-					}
-					if b3 == b'\n' {
-						counts.max_line_length = counts.max_line_length.max(current_line_length);
-						current_line_length = 0;
-					} else {
-						current_line_length += 1;
-					}
-					if b4 == b'\n' {
-						counts.max_line_length = counts.max_line_length.max(current_line_length);
-						current_line_length = 0;
-					} else {
-						current_line_length += 1;
-					}
-					if b5 == b'\n' {
-						counts.max_line_length = counts.max_line_length.max(current_line_length);
-						current_line_length = 0;
-					} else {
-						current_line_length += 1;
-					}
-					if b6 == b'\n' {
-						counts.max_line_length = counts.max_line_length.max(current_line_length);
-						current_line_length = 0;
-					} else {
-						current_line_length += 1;
-					}
-					if b7 == b'\n' {
-						counts.max_line_length = counts.max_line_length.max(current_line_length);
-						current_line_length = 0;
-					} else {
-						current_line_length += 1;
-					}
-				}
 				if app.words {
 					let ws0 = IS_WHITESPACE[b0 as usize];
 					let ws1 = IS_WHITESPACE[b1 as usize];
@@ -293,20 +655,12 @@ fn process_file(path: &Path, metadata: Option<&Metadata>, app: &Args) -> io::Res
 				if app.bytes {
 					counts.bytes += 1;
 				}
-				if app.chars && byte != 0 {
+				if app.chars && byte & 0xC0 != 0x80 {
 					counts.chars += 1;
 				}
 				if app.lines && byte == b'\n' {
 					counts.lines += 1;
 				}
-				if app.max_line_length {
-					if byte == b'\n' {
-						counts.max_line_length = counts.max_line_length.max(current_line_length);
-						current_line_length = 0;
-					} else {
-						current_line_length += 1;
-					}
-				}
 				if app.words {
 					let is_whitespace = IS_WHITESPACE[byte as usize];
 					if !in_word && !is_whitespace {
@@ -324,12 +678,12 @@ fn process_file(path: &Path, metadata: Option<&Metadata>, app: &Args) -> io::Res
 	if app.words && in_word {
 		counts.words += 1;
 	}
-	if app.max_line_length && current_line_length > 0 {
-		counts.max_line_length = counts.max_line_length.max(current_line_length);
+	if app.max_line_length {
+		counts.max_line_length = width_state.finish();
 	}
 
 	// Fallback to metadata for bytes if needed
-	if app.bytes && counts.bytes == 0 {
+	if app.bytes && counts.bytes == 0 && !app.decompress {
 		if let Some(meta) = metadata {
 			counts.bytes = meta.len() as usize;
 		}
@@ -356,3 +710,97 @@ fn append_counts(result: &mut String, counts: &Counts, app: &Args) {
 		let _ = write!(result, " max line length: {}", counts.max_line_length);
 	}
 }
+
+// Width of the largest requested field across every file plus the total, for `-P` column alignment
+fn posix_column_width(per_file: &[(Counts, String)], total: &Counts, app: &Args) -> usize {
+	let mut max_value = 0;
+	for counts in per_file.iter().map(|(counts, _)| counts).chain(std::iter::once(total)) {
+		if app.lines {
+			max_value = max_value.max(counts.lines);
+		}
+		if app.words {
+			max_value = max_value.max(counts.words);
+		}
+		if app.chars {
+			max_value = max_value.max(counts.chars);
+		}
+		if app.bytes {
+			max_value = max_value.max(counts.bytes);
+		}
+		if app.max_line_length {
+			max_value = max_value.max(counts.max_line_length);
+		}
+	}
+	max_value.to_string().len()
+}
+
+// Appends counts as GNU wc's fixed-order, right-aligned columns (lines words chars bytes max-line-length)
+#[inline(always)]
+fn append_counts_posix(result: &mut String, counts: &Counts, app: &Args, width: usize) {
+	let mut push_field = |value: usize| {
+		if !result.is_empty() {
+			result.push(' ');
+		}
+		let _ = write!(result, "{:width$}", value, width = width);
+	};
+	if app.lines {
+		push_field(counts.lines);
+	}
+	if app.words {
+		push_field(counts.words);
+	}
+	if app.chars {
+		push_field(counts.chars);
+	}
+	if app.bytes {
+		push_field(counts.bytes);
+	}
+	if app.max_line_length {
+		push_field(counts.max_line_length);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn app(flags: &[&str]) -> Args {
+		Args::parse_from(std::iter::once("ripwc").chain(flags.iter().copied()))
+	}
+
+	#[test]
+	fn word_split_across_two_chunks() {
+		let app = app(&["-w"]);
+		let chunks = ["hello wor", "ld foo"].map(|c| scan_chunk(c.as_bytes(), &app));
+		assert_eq!(merge_chunk_results(&chunks).words, 3);
+	}
+
+	#[test]
+	fn word_split_across_three_chunks() {
+		let app = app(&["-w"]);
+		let chunks = ["hello wor", "l", "d foo"].map(|c| scan_chunk(c.as_bytes(), &app));
+		assert_eq!(merge_chunk_results(&chunks).words, 3);
+	}
+
+	#[test]
+	fn line_split_across_two_chunks() {
+		let app = app(&["-L"]);
+		let chunks = ["hello wor", "ld\nfoo"].map(|c| scan_chunk(c.as_bytes(), &app));
+		assert_eq!(merge_chunk_results(&chunks).max_line_length, 11);
+	}
+
+	#[test]
+	fn line_split_across_three_or_more_chunks() {
+		let app = app(&["-L"]);
+		let chunks = [vec![b'a'; 1000], vec![b'a'; 1000], vec![b'a'; 1000], vec![b'a'; 1000]].map(|c| scan_chunk(&c, &app));
+		assert_eq!(merge_chunk_results(&chunks).max_line_length, 4000);
+	}
+
+	#[test]
+	fn multibyte_char_split_at_chunk_boundary() {
+		let app = app(&["-L"]);
+		let e_bytes = "é".as_bytes();
+		let chunks = [&e_bytes[..1], &e_bytes[1..]].map(|c| scan_chunk(c, &app));
+		assert_eq!(merge_chunk_results(&chunks).max_line_length, 1);
+	}
+}